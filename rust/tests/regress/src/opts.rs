@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Command-line options for the regress test runner.
+#[derive(Parser, Debug, Clone)]
+pub struct Opts {
+    /// Name of the database to create and run the regress tests against.
+    #[clap(long)]
+    database_name: String,
+
+    /// Host of the Postgres/RisingWave instance to connect to. Defaults to the local socket
+    /// `psql` would otherwise use when unset.
+    #[clap(long)]
+    host: Option<String>,
+
+    /// Port of the Postgres/RisingWave instance to connect to.
+    #[clap(long)]
+    port: Option<u16>,
+
+    /// User to connect as.
+    #[clap(long)]
+    user: Option<String>,
+
+    /// Inline password to connect with. Mutually exclusive with `password_file`; prefer
+    /// `password_file` so the secret doesn't end up in shell history or process listings.
+    #[clap(long)]
+    password: Option<String>,
+
+    /// Path to a file holding the password to connect with. Mutually exclusive with `password`.
+    #[clap(long)]
+    password_file: Option<PathBuf>,
+}
+
+impl Opts {
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    pub fn host(&self) -> Option<String> {
+        self.host.clone()
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    pub fn user(&self) -> Option<String> {
+        self.user.clone()
+    }
+
+    pub fn password(&self) -> Option<String> {
+        self.password.clone()
+    }
+
+    pub fn password_file(&self) -> Option<PathBuf> {
+        self.password_file.clone()
+    }
+}
@@ -7,6 +7,7 @@ const PG_DB_NAME: &str = "postgres";
 
 pub(crate) struct Psql {
     opts: Opts,
+    conn: PsqlConnConfig,
 }
 
 pub(crate) struct PsqlCommandBuilder {
@@ -14,26 +15,71 @@ pub(crate) struct PsqlCommandBuilder {
     cmd: Command,
 }
 
+/// Connection details threaded into every `psql` invocation, so the init/create/drop flow can
+/// run against a remote or secured Postgres instead of always shelling out to a local one.
+struct PsqlConnConfig {
+    host: Option<String>,
+    port: Option<u16>,
+    user: Option<String>,
+    /// Kept out of argv; set as `PGPASSWORD` on the spawned command. Populated either from an
+    /// inline password or, if a password file was configured, from that file's (trimmed)
+    /// contents, so a bad file path is a hard error here instead of a silently degraded run.
+    password: Option<String>,
+}
+
+impl PsqlConnConfig {
+    fn from_opts(opts: &Opts) -> anyhow::Result<Self> {
+        let password = opts.password();
+        let password_file = opts.password_file();
+        if password.is_some() && password_file.is_some() {
+            bail!("only one of an inline password and a password file may be configured");
+        }
+
+        let password = match password_file {
+            Some(password_file) => Some(
+                std::fs::read_to_string(&password_file)
+                    .with_context(|| {
+                        format!(
+                            "failed to read password file {}",
+                            password_file.display()
+                        )
+                    })?
+                    .trim()
+                    .to_string(),
+            ),
+            None => password,
+        };
+
+        Ok(Self {
+            host: opts.host(),
+            port: opts.port(),
+            user: opts.user(),
+            password,
+        })
+    }
+}
+
 impl Psql {
-    pub(crate) fn new(opts: Opts) -> Self {
-        Self { opts }
+    pub(crate) fn new(opts: Opts) -> anyhow::Result<Self> {
+        let conn = PsqlConnConfig::from_opts(&opts)?;
+        Ok(Self { opts, conn })
     }
 
     pub(crate) async fn init(&self) -> anyhow::Result<()> {
         info!("Initializing instances.");
 
         for db in [self.opts.database_name(), PG_DB_NAME] {
-            Psql::drop_database_if_exists(db).await?;
-            Psql::create_database(db).await?;
+            self.drop_database_if_exists(db).await?;
+            self.create_database(db).await?;
         }
 
         Ok(())
     }
 
-    pub(crate) async fn create_database<S: AsRef<str>>(db: S) -> anyhow::Result<()> {
+    pub(crate) async fn create_database<S: AsRef<str>>(&self, db: S) -> anyhow::Result<()> {
         info!("Creating database {}", db.as_ref());
 
-        let mut cmd = PsqlCommandBuilder::new(PG_DB_NAME)
+        let mut cmd = PsqlCommandBuilder::new(PG_DB_NAME, &self.conn)
             .add_cmd(format!(
                 r#"CREATE DATABASE "{}" TEMPLATE=template0 LC_COLLATE='C' LC_CTYPE='C'"#,
                 db.as_ref()
@@ -52,10 +98,10 @@ impl Psql {
         }
     }
 
-    pub(crate) async fn drop_database_if_exists<S: AsRef<str>>(db: S) -> anyhow::Result<()> {
+    pub(crate) async fn drop_database_if_exists<S: AsRef<str>>(&self, db: S) -> anyhow::Result<()> {
         info!("Dropping database {} if exists", db.as_ref());
 
-        let mut cmd = PsqlCommandBuilder::new("postgres")
+        let mut cmd = PsqlCommandBuilder::new("postgres", &self.conn)
             .add_cmd("SET client_min_messages = warning")
             .add_cmd(format!(r#"DROP DATABASE IF EXISTS "{}""#, db.as_ref()))
             .build();
@@ -75,10 +121,28 @@ impl Psql {
 }
 
 impl PsqlCommandBuilder {
-    pub(crate) fn new<S: ToString>(database: S) -> Self {
+    pub(crate) fn new<S: ToString>(database: S, conn: &PsqlConnConfig) -> Self {
         let mut cmd = Command::new("psql");
         cmd.arg("-X");
 
+        if let Some(host) = &conn.host {
+            cmd.args(["-h", host]);
+        }
+        if let Some(port) = conn.port {
+            cmd.args(["-p", &port.to_string()]);
+        }
+        if let Some(user) = &conn.user {
+            cmd.args(["-U", user]);
+        }
+
+        // Keep the secret out of the process argv: set it via `PGPASSWORD` instead. `PGPASSFILE`
+        // is not used here since it requires a `.pgpass`-formatted file
+        // (`hostname:port:database:username:password` per line) with `0600` permissions, not a
+        // file holding a bare password.
+        if let Some(password) = &conn.password {
+            cmd.env("PGPASSWORD", password);
+        }
+
         Self {
             database: database.to_string(),
             cmd,
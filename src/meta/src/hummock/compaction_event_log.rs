@@ -0,0 +1,217 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lock-free collector for observing every compaction event dispatched/reported by
+//! [`super::mock_hummock_meta_client::MockHummockMetaClient`] in tests, without racing on the
+//! live `subscribe_compaction_event` channels.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use risingwave_hummock_sdk::compact_task::CompactTask;
+use risingwave_pb::hummock::subscribe_compaction_event_request::ReportTask;
+use tokio::task::JoinHandle;
+
+/// A single compaction event captured by [`CompactionEventCollector`].
+#[derive(Debug, Clone)]
+pub enum CompactionEventRecord {
+    /// A `CompactTask` handed to a compactor, either by the automatic pick loop or by
+    /// `trigger_manual_compaction`.
+    Dispatched {
+        task_id: u64,
+        create_at: u64,
+        task: CompactTask,
+    },
+    /// A `ReportTask` received back from a compactor.
+    Reported {
+        task_id: u64,
+        create_at: u64,
+        report: ReportTask,
+    },
+}
+
+impl CompactionEventRecord {
+    pub fn task_id(&self) -> u64 {
+        match self {
+            Self::Dispatched { task_id, .. } | Self::Reported { task_id, .. } => *task_id,
+        }
+    }
+
+    fn create_at(&self) -> u64 {
+        match self {
+            Self::Dispatched { create_at, .. } | Self::Reported { create_at, .. } => *create_at,
+        }
+    }
+}
+
+/// Slot has not been claimed by a producer; a producer may transition it to `WRITING`.
+const SLOT_EMPTY: u8 = 0;
+/// A producer has claimed the slot and is writing its `UnsafeCell`; no one else may touch it.
+const SLOT_WRITING: u8 = 1;
+/// A producer finished publishing a record; the collector may drain it back to `EMPTY`.
+const SLOT_READY: u8 = 2;
+
+struct Slot {
+    state: AtomicU8,
+    record: UnsafeCell<Option<CompactionEventRecord>>,
+}
+
+impl Default for Slot {
+    fn default() -> Self {
+        Self {
+            state: AtomicU8::new(SLOT_EMPTY),
+            record: UnsafeCell::new(None),
+        }
+    }
+}
+
+// SAFETY: a slot's `UnsafeCell` is only touched by whichever side currently holds the `state`
+// handoff: a producer writes it only after winning the `EMPTY -> WRITING` CAS below, and the
+// collector reads it only after winning the `READY -> EMPTY` CAS in `drain_ready`. Those two CASes
+// can never both succeed on the same slot at once, so there is no unsynchronized concurrent access
+// to the `UnsafeCell`.
+unsafe impl Sync for Slot {}
+
+/// Fixed-size multi-producer ring buffer: producers reserve a slot with a single atomic
+/// `fetch_add` and never take a lock to publish into it. A single background task drains ready
+/// slots into an ordered log.
+struct CompactionEventRing {
+    slots: Box<[Slot]>,
+    next: AtomicU64,
+}
+
+impl CompactionEventRing {
+    fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity.max(1)).map(|_| Slot::default()).collect(),
+            next: AtomicU64::new(0),
+        }
+    }
+
+    /// Reserves the next slot and publishes `record` into it. If the ring wraps around onto a
+    /// slot that the collector hasn't drained yet (or that another producer is still writing),
+    /// this yields back to the runtime until that slot is free rather than racing the
+    /// `UnsafeCell`. A raw CPU spin would never be scheduled out on a current-thread runtime (the
+    /// single-threaded flavor `#[tokio::test]` uses), starving the background drain task that's
+    /// supposed to free the slot.
+    async fn push(&self, record: CompactionEventRecord) {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) as usize % self.slots.len();
+        let slot = &self.slots[index];
+        while slot
+            .state
+            .compare_exchange_weak(
+                SLOT_EMPTY,
+                SLOT_WRITING,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            tokio::task::yield_now().await;
+        }
+        // SAFETY: we hold the slot in `WRITING` state, which only we transitioned into, so no
+        // other producer or the collector may touch the `UnsafeCell` until we publish `READY`.
+        unsafe {
+            *slot.record.get() = Some(record);
+        }
+        slot.state.store(SLOT_READY, Ordering::Release);
+    }
+
+    /// Drains every slot currently in the `READY` state.
+    fn drain_ready(&self) -> Vec<CompactionEventRecord> {
+        let mut drained = Vec::new();
+        for slot in &self.slots {
+            if slot
+                .state
+                .compare_exchange(SLOT_READY, SLOT_EMPTY, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                // SAFETY: we won the `READY -> EMPTY` transition, so the producer that wrote
+                // this slot is done with it and no one will touch it again until the next
+                // `EMPTY -> WRITING` transition.
+                if let Some(record) = unsafe { (*slot.record.get()).take() } {
+                    drained.push(record);
+                }
+            }
+        }
+        drained
+    }
+}
+
+/// Opt-in, lock-free collector of every compaction event dispatched and reported through a
+/// [`super::mock_hummock_meta_client::MockHummockMetaClient`]. Producers push into a
+/// [`CompactionEventRing`] without ever taking a mutex; a background task drains ready slots into
+/// an ordered log that tests can query.
+pub struct CompactionEventCollector {
+    ring: Arc<CompactionEventRing>,
+    log: Arc<Mutex<Vec<CompactionEventRecord>>>,
+    drain_handle: JoinHandle<()>,
+}
+
+impl CompactionEventCollector {
+    pub fn new(ring_capacity: usize) -> Self {
+        let ring = Arc::new(CompactionEventRing::new(ring_capacity));
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let drain_ring = ring.clone();
+        let drain_log = log.clone();
+        let drain_handle = tokio::spawn(async move {
+            loop {
+                let mut drained = drain_ring.drain_ready();
+                if drained.is_empty() {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    continue;
+                }
+                let mut log = drain_log.lock().unwrap();
+                log.append(&mut drained);
+                log.sort_by_key(|record| record.create_at());
+            }
+        });
+
+        Self {
+            ring,
+            log,
+            drain_handle,
+        }
+    }
+
+    /// Records an event. Safe to call from any number of concurrent producers.
+    pub async fn record(&self, record: CompactionEventRecord) {
+        self.ring.push(record).await;
+    }
+
+    /// Returns a snapshot of every event recorded so far, ordered by `create_at`.
+    pub fn all_events(&self) -> Vec<CompactionEventRecord> {
+        self.log.lock().unwrap().clone()
+    }
+
+    /// Returns a snapshot of every event recorded for `task_id`, ordered by `create_at`.
+    pub fn events_for_task(&self, task_id: u64) -> Vec<CompactionEventRecord> {
+        self.log
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|record| record.task_id() == task_id)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Drop for CompactionEventCollector {
+    fn drop(&mut self) {
+        self.drain_handle.abort();
+    }
+}
@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -44,23 +44,67 @@ use risingwave_pb::hummock::{
 use risingwave_rpc_client::error::{Result, RpcError};
 use risingwave_rpc_client::{CompactionEventItem, HummockMetaClient};
 use thiserror_ext::AsReport;
-use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::sync::mpsc::{channel, unbounded_channel, Sender, UnboundedSender};
 use tokio::task::JoinHandle;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::hummock::compaction::selector::{
-    default_compaction_selector, CompactionSelector, SpaceReclaimCompactionSelector,
+    default_compaction_selector, CompactionSelector, ManualCompactionOption,
+    ManualCompactionSelector, SpaceReclaimCompactionSelector,
 };
+use crate::hummock::compaction_event_log::{CompactionEventCollector, CompactionEventRecord};
 use crate::hummock::{CommitEpochInfo, HummockManager, NewTableFragmentInfo};
 
+/// Per-compaction-group quota enforced by [`MockHummockMetaClient::commit_epoch`], mirroring a
+/// bucket quota backed by object/size counters.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CompactionGroupQuota {
+    pub max_objects: Option<u64>,
+    pub max_bytes: Option<u64>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CompactionGroupCounter {
+    objects: u64,
+    bytes: u64,
+}
+
 pub struct MockHummockMetaClient {
     hummock_manager: Arc<HummockManager>,
     context_id: HummockContextId,
     compact_context_id: AtomicU32,
     // used for hummock replay to avoid collision with existing sst files
     sst_offset: u64,
+    // populated once `subscribe_compaction_event` is called, so manually triggered tasks can be
+    // pushed through the same response stream the automatic compaction loop uses.
+    compaction_event_tx: std::sync::Mutex<Option<Sender<CompactionEventItem>>>,
+    quotas: std::sync::Mutex<HashMap<u64, CompactionGroupQuota>>,
+    // `Arc`-wrapped so the `subscribe_compaction_event` report loop (a `tokio::spawn`'d `'static`
+    // task with no access to `self`) can repair a group's counter when a task is reported back.
+    counters: Arc<std::sync::Mutex<HashMap<u64, CompactionGroupCounter>>>,
+    // bounded capacity of the `CompactTask`/`ReportTask` channels backing
+    // `subscribe_compaction_event`, so a slow consumer applies back-pressure instead of letting
+    // the mock buffer unboundedly.
+    compaction_event_channel_capacity: usize,
+    // opt-in observer of every `CompactTask`/`ReportTask` that flows through this client
+    event_collector: Option<Arc<CompactionEventCollector>>,
+    // input object/byte totals of every in-flight `CompactTask`, keyed by task id, so the
+    // per-compaction-group counters can be repaired by the true delta (output - input) once the
+    // task is reported back instead of only ever growing from `commit_epoch`.
+    pending_compaction_tasks: Arc<std::sync::Mutex<HashMap<u64, PendingCompactionTask>>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingCompactionTask {
+    compaction_group_id: u64,
+    input_objects: u64,
+    input_bytes: u64,
 }
 
+/// Default capacity of the bounded compaction-event channels, chosen to absorb a small burst of
+/// auto-picked tasks without masking a genuinely stuck consumer.
+const DEFAULT_COMPACTION_EVENT_CHANNEL_CAPACITY: usize = 16;
+
 impl MockHummockMetaClient {
     pub fn new(
         hummock_manager: Arc<HummockManager>,
@@ -71,6 +115,12 @@ impl MockHummockMetaClient {
             context_id,
             compact_context_id: AtomicU32::new(context_id),
             sst_offset: 0,
+            compaction_event_tx: std::sync::Mutex::new(None),
+            quotas: std::sync::Mutex::new(HashMap::new()),
+            counters: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            compaction_event_channel_capacity: DEFAULT_COMPACTION_EVENT_CHANNEL_CAPACITY,
+            event_collector: None,
+            pending_compaction_tasks: Arc::new(std::sync::Mutex::new(HashMap::new())),
         }
     }
 
@@ -84,6 +134,103 @@ impl MockHummockMetaClient {
             context_id,
             compact_context_id: AtomicU32::new(context_id),
             sst_offset,
+            compaction_event_tx: std::sync::Mutex::new(None),
+            quotas: std::sync::Mutex::new(HashMap::new()),
+            counters: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            compaction_event_channel_capacity: DEFAULT_COMPACTION_EVENT_CHANNEL_CAPACITY,
+            event_collector: None,
+            pending_compaction_tasks: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Overrides the bounded capacity of the compaction-event channels used by
+    /// `subscribe_compaction_event`. Must be called before `subscribe_compaction_event`.
+    pub fn with_compaction_event_channel_capacity(mut self, capacity: usize) -> Self {
+        self.compaction_event_channel_capacity = capacity;
+        self
+    }
+
+    /// Opts this client into recording every `CompactTask`/`ReportTask` it dispatches or
+    /// receives into a [`CompactionEventCollector`], so tests can inspect the full compaction
+    /// history without racing on the live channels.
+    pub fn with_event_collector(mut self, ring_capacity: usize) -> Self {
+        self.event_collector = Some(Arc::new(CompactionEventCollector::new(ring_capacity)));
+        self
+    }
+
+    /// Returns the event collector, if one was attached with `with_event_collector`.
+    pub fn event_collector(&self) -> Option<&Arc<CompactionEventCollector>> {
+        self.event_collector.as_ref()
+    }
+
+    /// Configures the object/size quota for `compaction_group_id`. Pass `CompactionGroupQuota`
+    /// with both fields `None` to lift any existing quota.
+    pub fn set_compaction_group_quota(&self, compaction_group_id: u64, quota: CompactionGroupQuota) {
+        self.quotas
+            .lock()
+            .unwrap()
+            .insert(compaction_group_id, quota);
+    }
+
+    /// Returns the `(object_count, total_bytes)` currently tracked for `compaction_group_id`.
+    pub fn compaction_group_counter(&self, compaction_group_id: u64) -> (u64, u64) {
+        let counter = self
+            .counters
+            .lock()
+            .unwrap()
+            .get(&compaction_group_id)
+            .copied()
+            .unwrap_or_default();
+        (counter.objects, counter.bytes)
+    }
+
+    fn adjust_compaction_group_counter(
+        &self,
+        compaction_group_id: u64,
+        delta_objects: i64,
+        delta_bytes: i64,
+    ) {
+        Self::adjust_compaction_group_counter_for(
+            &self.counters,
+            compaction_group_id,
+            delta_objects,
+            delta_bytes,
+        );
+    }
+
+    /// Same as `adjust_compaction_group_counter`, but takes `counters` directly so it can be
+    /// called from the `subscribe_compaction_event` report loop, which only holds a clone of the
+    /// `Arc` rather than `&self`.
+    fn adjust_compaction_group_counter_for(
+        counters: &std::sync::Mutex<HashMap<u64, CompactionGroupCounter>>,
+        compaction_group_id: u64,
+        delta_objects: i64,
+        delta_bytes: i64,
+    ) {
+        let mut counters = counters.lock().unwrap();
+        let counter = counters.entry(compaction_group_id).or_default();
+        counter.objects = (counter.objects as i64 + delta_objects).max(0) as u64;
+        counter.bytes = (counter.bytes as i64 + delta_bytes).max(0) as u64;
+    }
+
+    /// Records the input object/byte totals of a dispatched `CompactTask` so the counter can be
+    /// repaired by the real (output - input) delta once the task is reported back, instead of
+    /// only ever growing.
+    fn track_pending_compaction_task(
+        compaction_group_id: u64,
+        task: &CompactTask,
+    ) -> PendingCompactionTask {
+        let (input_objects, input_bytes) = task
+            .input_ssts
+            .iter()
+            .flat_map(|input_level| &input_level.table_infos)
+            .fold((0u64, 0u64), |(objects, bytes), sst| {
+                (objects + 1, bytes + sst.file_size)
+            });
+        PendingCompactionTask {
+            compaction_group_id,
+            input_objects,
+            input_bytes,
         }
     }
 
@@ -178,6 +325,56 @@ impl HummockMetaClient for MockHummockMetaClient {
             .map(|table_id| table_id.table_id)
             .collect::<BTreeSet<_>>();
 
+        // Attribute each sstable's objects/bytes to the compaction group its tables actually
+        // belong to (falling back to the default group for tables not yet tracked in the current
+        // version, e.g. a table being created by this very commit), rather than folding every
+        // commit into one global bucket.
+        let mut new_group_totals: HashMap<u64, (u64, u64)> = HashMap::new();
+        for sstable in &sync_result.uncommitted_ssts {
+            let group_id = sstable
+                .sst_info
+                .table_ids
+                .iter()
+                .find_map(|&table_id| {
+                    version
+                        .state_table_info
+                        .info()
+                        .get(&TableId::from(table_id))
+                        .map(|info| info.compaction_group_id)
+                })
+                .unwrap_or_else(|| StaticCompactionGroupId::StateDefault.into());
+            let totals = new_group_totals.entry(group_id).or_insert((0, 0));
+            totals.0 += 1;
+            totals.1 += sstable.sst_info.file_size;
+        }
+
+        {
+            let quotas = self.quotas.lock().unwrap();
+            for (&group_id, &(new_objects, new_bytes)) in &new_group_totals {
+                let Some(quota) = quotas.get(&group_id) else {
+                    continue;
+                };
+                let (committed_objects, committed_bytes) = self.compaction_group_counter(group_id);
+                if quota
+                    .max_objects
+                    .is_some_and(|max| committed_objects + new_objects > max)
+                    || quota
+                        .max_bytes
+                        .is_some_and(|max| committed_bytes + new_bytes > max)
+                {
+                    return Err(anyhow!(
+                        "compaction group {} quota exceeded: {}/{:?} objects, {}/{:?} bytes",
+                        group_id,
+                        committed_objects + new_objects,
+                        quota.max_objects,
+                        committed_bytes + new_bytes,
+                        quota.max_bytes
+                    )
+                    .into());
+                }
+            }
+        }
+
         let old_value_ssts_vec = if is_log_store {
             sync_result.old_value_ssts.clone()
         } else {
@@ -250,6 +447,9 @@ impl HummockMetaClient for MockHummockMetaClient {
             })
             .await
             .map_err(mock_err)?;
+        for (group_id, (new_objects, new_bytes)) in new_group_totals {
+            self.adjust_compaction_group_counter(group_id, new_objects as i64, new_bytes as i64);
+        }
         Ok(())
     }
 
@@ -259,29 +459,96 @@ impl HummockMetaClient for MockHummockMetaClient {
 
     async fn trigger_manual_compaction(
         &self,
-        _compaction_group_id: u64,
-        _table_id: u32,
-        _level: u32,
-        _sst_ids: Vec<u64>,
+        compaction_group_id: u64,
+        table_id: u32,
+        level: u32,
+        sst_ids: Vec<u64>,
     ) -> Result<()> {
-        todo!()
+        let option = ManualCompactionOption {
+            sst_ids,
+            level: level as usize,
+            internal_table_id: std::collections::HashSet::from([table_id]),
+            ..Default::default()
+        };
+        let mut selector: Box<dyn CompactionSelector> =
+            Box::new(ManualCompactionSelector::new(option));
+        let task = self
+            .hummock_manager
+            .get_compact_task(compaction_group_id, &mut selector)
+            .await
+            .map_err(mock_err)?;
+        let Some(task) = task else {
+            return Ok(());
+        };
+
+        let tx = self.compaction_event_tx.lock().unwrap().clone();
+        // `hummock_manager.get_compact_task` has already reserved `task` against the manager's
+        // state; without a live stream to deliver it through, it would never be reported back
+        // and would leak as a permanently pending task.
+        let Some(tx) = tx else {
+            return Err(anyhow!(
+                "cannot deliver manually triggered compaction task {}: subscribe_compaction_event \
+                 has not been called yet",
+                task.task_id
+            )
+            .into());
+        };
+
+        self.pending_compaction_tasks.lock().unwrap().insert(
+            task.task_id,
+            Self::track_pending_compaction_task(compaction_group_id, &task),
+        );
+
+        let create_at = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Clock may have gone backwards")
+            .as_millis() as u64;
+        if let Some(collector) = &self.event_collector {
+            collector
+                .record(CompactionEventRecord::Dispatched {
+                    task_id: task.task_id,
+                    create_at,
+                    task: task.clone(),
+                })
+                .await;
+        }
+        let resp = SubscribeCompactionEventResponse {
+            event: Some(ResponseEvent::CompactTask(task.into())),
+            create_at,
+        };
+        let _ = tx.send(Ok(resp)).await;
+        Ok(())
     }
 
     async fn report_full_scan_task(
         &self,
-        _filtered_object_ids: Vec<HummockSstableObjectId>,
-        _total_object_count: u64,
-        _total_object_size: u64,
+        filtered_object_ids: Vec<HummockSstableObjectId>,
+        total_object_count: u64,
+        total_object_size: u64,
     ) -> Result<()> {
-        unimplemented!()
+        self.hummock_manager
+            .complete_full_gc(filtered_object_ids, total_object_count, total_object_size)
+            .await
+            .map_err(mock_err)?;
+        // Full-GC candidates are objects no longer referenced by the current `HummockVersion`,
+        // i.e. they already fell out of their compaction group's live working set when an
+        // earlier compaction superseded them — at which point `report_compact_task` handling
+        // already repaired that group's counter. Adjusting a counter here again would double
+        // count the same removal, and full-GC has no reliable way to attribute a scanned
+        // object's size back to a specific compaction group anyway.
+        Ok(())
     }
 
     async fn trigger_full_gc(
         &self,
-        _sst_retention_time_sec: u64,
-        _prefix: Option<String>,
+        sst_retention_time_sec: u64,
+        prefix: Option<String>,
     ) -> Result<()> {
-        unimplemented!()
+        self.hummock_manager
+            .start_full_gc(Duration::from_secs(sst_retention_time_sec), prefix)
+            .await
+            .map_err(mock_err)?;
+        Ok(())
     }
 
     async fn subscribe_compaction_event(
@@ -314,11 +581,21 @@ impl HummockMetaClient for MockHummockMetaClient {
 
         self.compact_context_id.store(context_id, Ordering::Release);
 
-        let (task_tx, task_rx) = tokio::sync::mpsc::unbounded_channel();
+        // Bounded so a slow consumer back-pressures task generation instead of the mock
+        // buffering an unbounded backlog of `CompactTask`s in memory.
+        let (task_tx, task_rx) = channel(self.compaction_event_channel_capacity);
+        *self.compaction_event_tx.lock().unwrap() = Some(task_tx.clone());
+        // Only the first fatal error encountered by either loop is surfaced through the stream;
+        // later ones are dropped once the stream is already being torn down.
+        let fatal_error_reported = Arc::new(std::sync::atomic::AtomicBool::new(false));
 
         let hummock_manager_compact = self.hummock_manager.clone();
         let mut join_handle_vec = vec![];
 
+        let dispatch_task_tx = task_tx.clone();
+        let dispatch_fatal_error_reported = fatal_error_reported.clone();
+        let dispatch_event_collector = self.event_collector.clone();
+        let dispatch_pending_compaction_tasks = self.pending_compaction_tasks.clone();
         let handle = tokio::spawn(async move {
             loop {
                 let group_and_type = hummock_manager_compact
@@ -339,20 +616,41 @@ impl HummockMetaClient for MockHummockMetaClient {
 
                     _ => panic!("Error type when mock_hummock_meta_client subscribe_compact_tasks"),
                 };
-                if let Some(task) = hummock_manager_compact
-                    .get_compact_task(group, &mut selector)
-                    .await
-                    .unwrap()
-                {
+                let task = match hummock_manager_compact.get_compact_task(group, &mut selector).await {
+                    Ok(task) => task,
+                    Err(e) => {
+                        if !dispatch_fatal_error_reported.swap(true, Ordering::SeqCst) {
+                            let _ = dispatch_task_tx.send(Err(mock_err(e))).await;
+                        }
+                        break;
+                    }
+                };
+                if let Some(task) = task {
+                    dispatch_pending_compaction_tasks.lock().unwrap().insert(
+                        task.task_id,
+                        MockHummockMetaClient::track_pending_compaction_task(group, &task),
+                    );
+                    let create_at = SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .expect("Clock may have gone backwards")
+                        .as_millis() as u64;
+                    if let Some(collector) = &dispatch_event_collector {
+                        collector
+                            .record(CompactionEventRecord::Dispatched {
+                                task_id: task.task_id,
+                                create_at,
+                                task: task.clone(),
+                            })
+                            .await;
+                    }
                     let resp = SubscribeCompactionEventResponse {
                         event: Some(ResponseEvent::CompactTask(task.into())),
-                        create_at: SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .expect("Clock may have gone backwards")
-                            .as_millis() as u64,
+                        create_at,
                     };
 
-                    let _ = task_tx.send(Ok(resp));
+                    if dispatch_task_tx.send(Ok(resp)).await.is_err() {
+                        break;
+                    }
                 }
             }
         });
@@ -360,30 +658,76 @@ impl HummockMetaClient for MockHummockMetaClient {
         join_handle_vec.push(handle);
 
         let hummock_manager_compact = self.hummock_manager.clone();
+        let report_task_tx = task_tx.clone();
+        let report_event_collector = self.event_collector.clone();
+        let report_pending_compaction_tasks = self.pending_compaction_tasks.clone();
+        let counters_for_report = self.counters.clone();
         let report_handle = tokio::spawn(async move {
             tracing::info!("report_handle start");
             loop {
+                // Match the dispatch loop's fail-fast semantics: once a fatal error has been
+                // surfaced through the stream, stop handling further reports instead of quietly
+                // continuing to call `report_compact_task` against state the client has already
+                // given up on.
+                if fatal_error_reported.load(Ordering::SeqCst) {
+                    break;
+                }
                 if let Some(item) = request_receiver.recv().await {
-                    if let Event::ReportTask(ReportTask {
-                        task_id,
-                        task_status,
-                        sorted_output_ssts,
-                        table_stats_change,
-                    }) = item.event.unwrap()
-                    {
-                        if let Err(e) = hummock_manager_compact
+                    if let Event::ReportTask(report) = item.event.unwrap() {
+                        let ReportTask {
+                            task_id,
+                            task_status,
+                            sorted_output_ssts,
+                            table_stats_change,
+                        } = report.clone();
+                        if let Some(collector) = &report_event_collector {
+                            collector
+                                .record(CompactionEventRecord::Reported {
+                                    task_id,
+                                    create_at: SystemTime::now()
+                                        .duration_since(std::time::UNIX_EPOCH)
+                                        .expect("Clock may have gone backwards")
+                                        .as_millis() as u64,
+                                    report,
+                                })
+                                .await;
+                        }
+                        let sorted_output_ssts = sorted_output_ssts
+                            .into_iter()
+                            .map(SstableInfo::from)
+                            .collect_vec();
+                        let report_result = hummock_manager_compact
                             .report_compact_task(
                                 task_id,
                                 TaskStatus::try_from(task_status).unwrap(),
-                                sorted_output_ssts
-                                    .into_iter()
-                                    .map(SstableInfo::from)
-                                    .collect_vec(),
+                                sorted_output_ssts.clone(),
                                 Some(table_stats_change),
                             )
-                            .await
-                        {
+                            .await;
+                        // Repair the owning group's counter by the true (output - input) delta
+                        // only when the task succeeded: a failed or cancelled task leaves its
+                        // input ssts in the version, so the counter is left as it was. The
+                        // pending entry is dropped in every case.
+                        let pending =
+                            report_pending_compaction_tasks.lock().unwrap().remove(&task_id);
+                        if let Some(pending) = pending.filter(|_| {
+                            task_status == TaskStatus::Success as i32 && report_result.is_ok()
+                        }) {
+                            let output_objects = sorted_output_ssts.len() as u64;
+                            let output_bytes: u64 =
+                                sorted_output_ssts.iter().map(|sst| sst.file_size).sum();
+                            MockHummockMetaClient::adjust_compaction_group_counter_for(
+                                &counters_for_report,
+                                pending.compaction_group_id,
+                                output_objects as i64 - pending.input_objects as i64,
+                                output_bytes as i64 - pending.input_bytes as i64,
+                            );
+                        }
+                        if let Err(e) = report_result {
                             tracing::error!(error = %e.as_report(), "report compact_tack fail");
+                            if !fatal_error_reported.swap(true, Ordering::SeqCst) {
+                                let _ = report_task_tx.send(Err(mock_err(e))).await;
+                            }
                         }
                     }
                 }
@@ -395,7 +739,7 @@ impl HummockMetaClient for MockHummockMetaClient {
         Ok((
             request_sender,
             Box::pin(CompactionEventItemStream {
-                inner: UnboundedReceiverStream::new(task_rx),
+                inner: ReceiverStream::new(task_rx),
                 _handle: join_handle_vec,
             }),
         ))
@@ -417,7 +761,7 @@ impl MockHummockMetaClient {
 }
 
 pub struct CompactionEventItemStream {
-    inner: UnboundedReceiverStream<CompactionEventItem>,
+    inner: ReceiverStream<CompactionEventItem>,
     _handle: Vec<JoinHandle<()>>,
 }
 
@@ -437,3 +781,93 @@ impl Stream for CompactionEventItemStream {
         self.inner.poll_next_unpin(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hummock::test_utils::setup_compute_env;
+
+    async fn new_test_client() -> (Arc<HummockManager>, MockHummockMetaClient) {
+        let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+        let context_id = worker_node.id;
+        let client = MockHummockMetaClient::new(hummock_manager.clone(), context_id);
+        (hummock_manager, client)
+    }
+
+    #[tokio::test]
+    async fn trigger_full_gc_and_report_full_scan_task_round_trip() {
+        // chunk0-1: full-GC retention filtering should reach the manager and complete cleanly
+        // even when there is nothing to collect.
+        let (_hummock_manager, client) = new_test_client().await;
+        client.trigger_full_gc(3600, None).await.unwrap();
+        client.report_full_scan_task(vec![], 0, 0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn manual_compaction_with_no_pending_task_is_a_noop() {
+        // chunk0-2: with nothing to compact, trigger_manual_compaction should return Ok(())
+        // without requiring a subscribed compaction-event stream.
+        let (_hummock_manager, client) = new_test_client().await;
+        let group_id: u64 = StaticCompactionGroupId::StateDefault.into();
+        client
+            .trigger_manual_compaction(group_id, 0, 0, vec![])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn compaction_group_quota_is_tracked_per_group() {
+        // chunk0-3: quota/counters are keyed per compaction group rather than a single global
+        // bucket, so a quota set on one group must not appear under another.
+        let (_hummock_manager, client) = new_test_client().await;
+        let group_id: u64 = StaticCompactionGroupId::StateDefault.into();
+        let other_group_id = group_id + 1;
+        assert_eq!(client.compaction_group_counter(group_id), (0, 0));
+
+        client.set_compaction_group_quota(
+            group_id,
+            CompactionGroupQuota {
+                max_objects: Some(10),
+                max_bytes: Some(1024),
+            },
+        );
+
+        let quotas = client.quotas.lock().unwrap();
+        let quota = quotas.get(&group_id).copied().unwrap();
+        assert_eq!(quota.max_objects, Some(10));
+        assert_eq!(quota.max_bytes, Some(1024));
+        assert!(quotas.get(&other_group_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn compaction_event_channel_capacity_is_configurable() {
+        // chunk0-4: the bounded channel capacity backing subscribe_compaction_event can be
+        // overridden per client instead of always using the default.
+        let (hummock_manager, _client) = new_test_client().await;
+        let client = MockHummockMetaClient::new(hummock_manager, 0)
+            .with_compaction_event_channel_capacity(4);
+        assert_eq!(client.compaction_event_channel_capacity, 4);
+    }
+
+    #[tokio::test]
+    async fn event_collector_records_reported_events() {
+        // chunk0-5: the lock-free collector should observe every event pushed to it, matching
+        // the intent of with_event_collector.
+        let collector = CompactionEventCollector::new(4);
+        collector
+            .record(CompactionEventRecord::Reported {
+                task_id: 1,
+                create_at: 1,
+                report: ReportTask {
+                    task_id: 1,
+                    task_status: 0,
+                    sorted_output_ssts: vec![],
+                    table_stats_change: Default::default(),
+                },
+            })
+            .await;
+        // give the background drain task a chance to run
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert_eq!(collector.events_for_task(1).len(), 1);
+    }
+}